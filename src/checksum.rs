@@ -0,0 +1,163 @@
+use crate::trs::Checksum;
+
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+
+/// Digest algorithms gh-trs knows how to compute for a downloaded file.
+///
+/// `r#type` strings follow the GA4GH convention: lowercase, hyphenated
+/// algorithm names (e.g. `"sha-256"`).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum ChecksumType {
+    #[serde(rename = "sha-256")]
+    Sha256,
+    #[serde(rename = "sha-512")]
+    Sha512,
+    #[serde(rename = "sha-1")]
+    Sha1,
+    #[serde(rename = "md5")]
+    Md5,
+}
+
+impl Default for ChecksumType {
+    fn default() -> Self {
+        ChecksumType::Sha256
+    }
+}
+
+impl ChecksumType {
+    /// The GA4GH TRS `type` string for this algorithm, e.g. `"sha-256"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChecksumType::Sha256 => "sha-256",
+            ChecksumType::Sha512 => "sha-512",
+            ChecksumType::Sha1 => "sha-1",
+            ChecksumType::Md5 => "md5",
+        }
+    }
+}
+
+enum Hasher {
+    Sha256(Sha256),
+    Sha512(Sha512),
+    Sha1(Sha1),
+    Md5(md5::Md5),
+}
+
+impl Hasher {
+    fn new(checksum_type: ChecksumType) -> Self {
+        match checksum_type {
+            ChecksumType::Sha256 => Hasher::Sha256(Sha256::new()),
+            ChecksumType::Sha512 => Hasher::Sha512(Sha512::new()),
+            ChecksumType::Sha1 => Hasher::Sha1(Sha1::new()),
+            ChecksumType::Md5 => Hasher::Md5(md5::Md5::new()),
+        }
+    }
+
+    fn update(&mut self, chunk: &[u8]) {
+        match self {
+            Hasher::Sha256(h) => h.update(chunk),
+            Hasher::Sha512(h) => h.update(chunk),
+            Hasher::Sha1(h) => h.update(chunk),
+            Hasher::Md5(h) => h.update(chunk),
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Hasher::Sha256(h) => format!("{:x}", h.finalize()),
+            Hasher::Sha512(h) => format!("{:x}", h.finalize()),
+            Hasher::Sha1(h) => format!("{:x}", h.finalize()),
+            Hasher::Md5(h) => format!("{:x}", h.finalize()),
+        }
+    }
+}
+
+/// Digest `bytes` with a single algorithm, e.g. to verify a mirror's
+/// fetched bytes against a recorded `config::SourceChecksum`.
+pub(crate) fn hash_bytes(bytes: &[u8], checksum_type: ChecksumType) -> String {
+    let mut hasher = Hasher::new(checksum_type);
+    hasher.update(bytes);
+    hasher.finalize_hex()
+}
+
+/// Compute a [`Checksum`] for every algorithm in `checksum_types` from
+/// `bytes` already in memory (e.g. from `download::fetch`), so the file is
+/// never downloaded more than once per call site.
+pub fn generate_checksums(bytes: &[u8], checksum_types: &[ChecksumType]) -> Vec<Checksum> {
+    checksum_types
+        .iter()
+        .map(|checksum_type| Checksum {
+            checksum: hash_bytes(bytes, *checksum_type),
+            r#type: checksum_type.as_str().to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum_type_serde_round_trip() -> Result<(), serde_json::Error> {
+        for (checksum_type, json) in [
+            (ChecksumType::Sha256, "\"sha-256\""),
+            (ChecksumType::Sha512, "\"sha-512\""),
+            (ChecksumType::Sha1, "\"sha-1\""),
+            (ChecksumType::Md5, "\"md5\""),
+        ] {
+            assert_eq!(serde_json::to_string(&checksum_type)?, json);
+            assert_eq!(serde_json::from_str::<ChecksumType>(json)?, checksum_type);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_checksum_type_as_str() {
+        assert_eq!(ChecksumType::Sha256.as_str(), "sha-256");
+        assert_eq!(ChecksumType::Sha512.as_str(), "sha-512");
+        assert_eq!(ChecksumType::Sha1.as_str(), "sha-1");
+        assert_eq!(ChecksumType::Md5.as_str(), "md5");
+    }
+
+    #[test]
+    fn test_hasher_finalize_hex() {
+        let mut sha256 = Hasher::new(ChecksumType::Sha256);
+        sha256.update(b"hello world");
+        assert_eq!(
+            sha256.finalize_hex(),
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+
+        let mut sha1 = Hasher::new(ChecksumType::Sha1);
+        sha1.update(b"hello world");
+        assert_eq!(sha1.finalize_hex(), "2aae6c35c94fcfb415dbe95f408b9ce91ee846ed");
+
+        let mut md5 = Hasher::new(ChecksumType::Md5);
+        md5.update(b"hello world");
+        assert_eq!(md5.finalize_hex(), "5eb63bbbe01eeed093cb22bb8f5acdc3");
+    }
+
+    #[test]
+    fn test_hash_bytes() {
+        assert_eq!(
+            hash_bytes(b"hello world", ChecksumType::Sha256),
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+    }
+
+    #[test]
+    fn test_generate_checksums_empty_algorithms() {
+        assert_eq!(generate_checksums(b"hello world", &[]), vec![]);
+    }
+
+    #[test]
+    fn test_generate_checksums_multiple_algorithms() {
+        let checksums = generate_checksums(b"hello world", &[ChecksumType::Sha256, ChecksumType::Md5]);
+        assert_eq!(checksums.len(), 2);
+        assert_eq!(checksums[0].r#type, "sha-256");
+        assert_eq!(checksums[1].r#type, "md5");
+    }
+}
@@ -1,5 +1,7 @@
+use crate::checksum::ChecksumType;
+
 use anyhow::{anyhow, Result};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::path::{Path, PathBuf};
 use url::Url;
 use uuid::Uuid;
@@ -11,6 +13,14 @@ pub struct Config {
     pub license: String,
     pub authors: Vec<String>,
     pub workflow: Workflow,
+    /// Digest algorithms to compute for each file and attach as GA4GH TRS
+    /// `Checksum` entries. Defaults to `sha-256` alone.
+    #[serde(default = "default_checksum_types")]
+    pub checksum: Vec<ChecksumType>,
+}
+
+fn default_checksum_types() -> Vec<ChecksumType> {
+    vec![ChecksumType::default()]
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
@@ -37,9 +47,74 @@ pub enum LanguageType {
     Smk,
 }
 
+/// Where a `FileSource` was sourced from. The generated TRS `ToolFile`
+/// lists every source's URL but does not carry this label.
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-pub struct File {
+#[serde(rename_all = "lowercase")]
+pub enum SourceRel {
+    Repository,
+    Mirror,
+    Webarchive,
+}
+
+/// The checksum a `FileSource` is expected to produce, used to skip a
+/// mirror whose fetched bytes have silently diverged from the original.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SourceChecksum {
+    pub checksum: String,
+    pub r#type: ChecksumType,
+}
+
+/// One location a file can be fetched from. A `File`/`TestFile` carries a
+/// list of these so a dead mirror doesn't break publishing or downstream
+/// clients; the downloader tries them in order, skipping any whose fetched
+/// bytes don't match `checksum`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct FileSource {
     pub url: Url,
+    pub rel: SourceRel,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<SourceChecksum>,
+}
+
+impl FileSource {
+    pub fn new(url: &Url, rel: SourceRel) -> Self {
+        Self {
+            url: url.clone(),
+            rel,
+            checksum: None,
+        }
+    }
+}
+
+/// Accepts either a bare URL string (kept for backward compatibility with
+/// existing configs) or an explicit list of `FileSource`s.
+fn deserialize_sources<'de, D>(deserializer: D) -> std::result::Result<Vec<FileSource>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum SourcesRepr {
+        Bare(Url),
+        List(Vec<FileSource>),
+    }
+    let sources = match SourcesRepr::deserialize(deserializer)? {
+        SourcesRepr::Bare(url) => vec![FileSource::new(&url, SourceRel::Repository)],
+        SourcesRepr::List(sources) => sources,
+    };
+    if sources.is_empty() {
+        return Err(serde::de::Error::custom(
+            "at least one source is required, got an empty list",
+        ));
+    }
+    Ok(sources)
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct File {
+    #[serde(rename = "url", deserialize_with = "deserialize_sources")]
+    sources: Vec<FileSource>,
     pub target: PathBuf,
     pub r#type: FileType,
 }
@@ -57,11 +132,22 @@ impl File {
                 .into(),
         };
         Ok(Self {
-            url: url.clone(),
-            target: target,
+            sources: vec![FileSource::new(url, SourceRel::Repository)],
+            target,
             r#type,
         })
     }
+
+    /// Every mirror this file can be fetched from, in try order.
+    pub fn sources(&self) -> &[FileSource] {
+        &self.sources
+    }
+
+    /// The source a plain `url`-based caller should treat as canonical,
+    /// i.e. the first one on record.
+    pub fn url(&self) -> &Url {
+        &self.sources[0].url
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
@@ -107,7 +193,8 @@ impl Default for Testing {
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct TestFile {
-    pub url: Url,
+    #[serde(rename = "url", deserialize_with = "deserialize_sources")]
+    sources: Vec<FileSource>,
     pub target: PathBuf,
     pub r#type: TestFileType,
 }
@@ -125,11 +212,22 @@ impl TestFile {
                 .into(),
         };
         Ok(Self {
-            url: url.clone(),
-            target: target,
+            sources: vec![FileSource::new(url, SourceRel::Repository)],
+            target,
             r#type,
         })
     }
+
+    /// Every mirror this file can be fetched from, in try order.
+    pub fn sources(&self) -> &[FileSource] {
+        &self.sources
+    }
+
+    /// The source a plain `url`-based caller should treat as canonical,
+    /// i.e. the first one on record.
+    pub fn url(&self) -> &Url {
+        &self.sources[0].url
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
@@ -150,7 +248,7 @@ mod tests {
         let url = Url::parse("https://example.com/path/to/file.txt")?;
         let target = PathBuf::from("path/to/file.txt");
         let file = File::new(&url, Some(&target), FileType::Primary)?;
-        assert_eq!(file.url, url);
+        assert_eq!(file.url(), &url);
         assert_eq!(file.target, target);
         assert_eq!(file.r#type, FileType::Primary);
         Ok(())
@@ -160,12 +258,46 @@ mod tests {
     fn test_file_new_no_target() -> Result<()> {
         let url = Url::parse("https://example.com/path/to/file.txt")?;
         let file = File::new(&url, None::<PathBuf>, FileType::Primary)?;
-        assert_eq!(file.url, url);
+        assert_eq!(file.url(), &url);
         assert_eq!(file.target, PathBuf::from("file.txt"));
         assert_eq!(file.r#type, FileType::Primary);
         Ok(())
     }
 
+    #[test]
+    fn test_file_deserialize_bare_url() -> Result<()> {
+        let yaml = "url: https://example.com/path/to/file.txt\ntarget: file.txt\ntype: primary\n";
+        let file: File = serde_yaml::from_str(yaml)?;
+        assert_eq!(file.sources.len(), 1);
+        assert_eq!(file.url().as_str(), "https://example.com/path/to/file.txt");
+        assert_eq!(file.sources[0].rel, SourceRel::Repository);
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_deserialize_mirror_list() -> Result<()> {
+        let yaml = "\
+url:
+  - url: https://example.com/path/to/file.txt
+    rel: repository
+  - url: https://mirror.example.com/path/to/file.txt
+    rel: mirror
+target: file.txt
+type: primary
+";
+        let file: File = serde_yaml::from_str(yaml)?;
+        assert_eq!(file.sources.len(), 2);
+        assert_eq!(file.sources[1].rel, SourceRel::Mirror);
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_deserialize_empty_source_list_is_rejected() {
+        let yaml = "url: []\ntarget: file.txt\ntype: primary\n";
+        let result: std::result::Result<File, _> = serde_yaml::from_str(yaml);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_testing_default() -> Result<()> {
         let testing = Testing::default();
@@ -179,7 +311,7 @@ mod tests {
         let url = Url::parse("https://example.com/path/to/file.txt")?;
         let target = PathBuf::from("path/to/file.txt");
         let file = TestFile::new(&url, Some(&target), TestFileType::WfParams)?;
-        assert_eq!(file.url, url);
+        assert_eq!(file.url(), &url);
         assert_eq!(file.target, target);
         assert_eq!(file.r#type, TestFileType::WfParams);
         Ok(())
@@ -189,9 +321,37 @@ mod tests {
     fn test_test_file_no_target() -> Result<()> {
         let url = Url::parse("https://example.com/path/to/file.txt")?;
         let file = TestFile::new(&url, None::<PathBuf>, TestFileType::WfParams)?;
-        assert_eq!(file.url, url);
+        assert_eq!(file.url(), &url);
         assert_eq!(file.target, PathBuf::from("file.txt"));
         assert_eq!(file.r#type, TestFileType::WfParams);
         Ok(())
     }
+
+    #[test]
+    fn test_test_file_deserialize_source_checksum() -> Result<()> {
+        let yaml = "\
+url:
+  - url: https://example.com/path/to/data.fq
+    rel: repository
+    checksum:
+      checksum: abc123
+      type: sha-256
+  - url: https://archive.example.com/path/to/data.fq
+    rel: webarchive
+target: data.fq
+type: wf_params
+";
+        let file: TestFile = serde_yaml::from_str(yaml)?;
+        assert_eq!(file.sources.len(), 2);
+        assert_eq!(
+            file.sources[0].checksum,
+            Some(SourceChecksum {
+                checksum: "abc123".to_string(),
+                r#type: ChecksumType::Sha256,
+            })
+        );
+        assert_eq!(file.sources[1].rel, SourceRel::Webarchive);
+        assert_eq!(file.sources[1].checksum, None);
+        Ok(())
+    }
 }
@@ -0,0 +1,139 @@
+use crate::config::{Config, Language, Workflow};
+
+use serde::{Deserialize, Serialize};
+
+/// Copies only the fields `other` sets into `self`, leaving the rest
+/// untouched. Nested structs implement this recursively.
+pub trait Merge {
+    type Override;
+
+    fn merge(&mut self, other: Self::Override);
+}
+
+/// CLI overrides for `config::Config`, applied on top of a loaded config.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigOverride {
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub authors: Option<Vec<String>>,
+    #[serde(default)]
+    pub workflow: Option<WorkflowOverride>,
+}
+
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct WorkflowOverride {
+    #[serde(default)]
+    pub language: Option<LanguageOverride>,
+}
+
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct LanguageOverride {
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+impl Merge for Config {
+    type Override = ConfigOverride;
+
+    fn merge(&mut self, other: Self::Override) {
+        if let Some(version) = other.version {
+            self.version = version;
+        }
+        if let Some(license) = other.license {
+            self.license = license;
+        }
+        if let Some(authors) = other.authors {
+            self.authors = authors;
+        }
+        if let Some(workflow) = other.workflow {
+            self.workflow.merge(workflow);
+        }
+    }
+}
+
+impl Merge for Workflow {
+    type Override = WorkflowOverride;
+
+    fn merge(&mut self, other: Self::Override) {
+        if let Some(language) = other.language {
+            self.language.merge(language);
+        }
+    }
+}
+
+impl Merge for Language {
+    type Override = LanguageOverride;
+
+    fn merge(&mut self, other: Self::Override) {
+        if let Some(version) = other.version {
+            self.version = Some(version);
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use crate::config::{File, FileType, LanguageType, Testing};
+    use url::Url;
+    use uuid::Uuid;
+
+    fn base_config() -> Config {
+        Config {
+            id: Uuid::new_v4(),
+            version: "1.0.0".to_string(),
+            license: "MIT".to_string(),
+            authors: vec!["octocat".to_string()],
+            workflow: Workflow {
+                name: "test-workflow".to_string(),
+                readme: Url::parse("https://example.com/README.md").unwrap(),
+                language: Language {
+                    r#type: Some(LanguageType::Cwl),
+                    version: Some("v1.0".to_string()),
+                },
+                files: vec![File::new(
+                    &Url::parse("https://example.com/main.cwl").unwrap(),
+                    None::<&str>,
+                    FileType::Primary,
+                )
+                .unwrap()],
+                testing: vec![Testing::default()],
+            },
+            checksum: vec![],
+        }
+    }
+
+    #[test]
+    fn test_merge_overrides_only_set_fields() {
+        let mut config = base_config();
+        let override_ = ConfigOverride {
+            version: Some("2.0.0".to_string()),
+            license: None,
+            authors: None,
+            workflow: Some(WorkflowOverride {
+                language: Some(LanguageOverride {
+                    version: Some("v1.1".to_string()),
+                }),
+            }),
+        };
+        config.merge(override_);
+
+        assert_eq!(config.version, "2.0.0");
+        assert_eq!(config.license, "MIT");
+        assert_eq!(config.authors, vec!["octocat".to_string()]);
+        assert_eq!(config.workflow.language.version, Some("v1.1".to_string()));
+        assert_eq!(config.workflow.language.r#type, Some(LanguageType::Cwl));
+    }
+
+    #[test]
+    fn test_merge_empty_override_is_noop() {
+        let mut config = base_config();
+        let unchanged = config.clone();
+        config.merge(ConfigOverride::default());
+        assert_eq!(config, unchanged);
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Descriptor bytes inlined into a `FileWrapper.content`.
+///
+/// Different TRS producers encode inlined blobs with different base64
+/// dialects, so decoding tries each known encoding in turn (standard,
+/// URL-safe, URL-safe unpadded, standard unpadded), falling back to a
+/// whitespace-stripped retry for MIME-style line-wrapped input, and always
+/// re-encodes using one canonical form: URL-safe, unpadded.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InlineContent(pub Vec<u8>);
+
+/// Decodings tried in order when reading a previously published
+/// `FileWrapper`, since the producer may not have used our canonical form.
+const DECODE_CONFIGS: &[base64::Config] = &[
+    base64::STANDARD,
+    base64::URL_SAFE,
+    base64::URL_SAFE_NO_PAD,
+    base64::STANDARD_NO_PAD,
+];
+
+impl InlineContent {
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Self(bytes.into())
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Serialize for InlineContent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&base64::encode_config(&self.0, base64::URL_SAFE_NO_PAD))
+    }
+}
+
+impl<'de> Deserialize<'de> for InlineContent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let encoded = String::deserialize(deserializer)?;
+        for config in DECODE_CONFIGS {
+            if let Ok(bytes) = base64::decode_config(&encoded, *config) {
+                return Ok(Self(bytes));
+            }
+        }
+        // MIME-style producers line-wrap with embedded whitespace/newlines;
+        // strip it and retry before giving up.
+        let stripped: String = encoded.chars().filter(|c| !c.is_whitespace()).collect();
+        for config in DECODE_CONFIGS {
+            if let Ok(bytes) = base64::decode_config(&stripped, *config) {
+                return Ok(Self(bytes));
+            }
+        }
+        Err(serde::de::Error::custom(
+            "content is not valid base64 in any known encoding (standard, URL-safe, or whitespace-wrapped variants)",
+        ))
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_canonical() -> Result<(), serde_json::Error> {
+        let content = InlineContent::new(b"hello world".to_vec());
+        let encoded = serde_json::to_string(&content)?;
+        assert_eq!(encoded, "\"aGVsbG8gd29ybGQ\"");
+        let decoded: InlineContent = serde_json::from_str(&encoded)?;
+        assert_eq!(decoded, content);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_standard_padded() -> Result<(), serde_json::Error> {
+        let decoded: InlineContent = serde_json::from_str("\"aGVsbG8gd29ybGQ=\"")?;
+        assert_eq!(decoded.as_bytes(), b"hello world");
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_url_safe() -> Result<(), serde_json::Error> {
+        let decoded: InlineContent = serde_json::from_str("\"PD4_Pz4-\"")?;
+        assert_eq!(decoded.as_bytes(), b"<>??>>");
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_whitespace_wrapped() -> Result<(), serde_json::Error> {
+        let decoded: InlineContent = serde_json::from_str("\"aGVsbG8g\\nd29ybGQ=\\n\"")?;
+        assert_eq!(decoded.as_bytes(), b"hello world");
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let result: Result<InlineContent, _> = serde_json::from_str("\"not valid base64!!\"");
+        assert!(result.is_err());
+    }
+}
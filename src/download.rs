@@ -0,0 +1,101 @@
+use crate::checksum;
+use crate::config::FileSource;
+
+use anyhow::{anyhow, Result};
+
+/// Fetch the first `source` that both responds successfully and, when a
+/// checksum is recorded, matches it.
+pub fn fetch(sources: &[FileSource]) -> Result<Vec<u8>> {
+    let mut last_err: Option<anyhow::Error> = None;
+    for source in sources {
+        match fetch_source(source) {
+            Ok(bytes) => return Ok(bytes),
+            Err(e) => last_err = Some(e),
+        }
+    }
+    Err(last_err.unwrap_or_else(|| anyhow!("No sources to fetch from")))
+}
+
+fn fetch_source(source: &FileSource) -> Result<Vec<u8>> {
+    let bytes = reqwest::blocking::get(source.url.as_str())
+        .map_err(|e| anyhow!("Failed to request {}: {}", source.url, e))?
+        .error_for_status()
+        .map_err(|e| anyhow!("Failed to fetch {}: {}", source.url, e))?
+        .bytes()
+        .map_err(|e| anyhow!("Failed to read {}: {}", source.url, e))?
+        .to_vec();
+
+    if let Some(expected) = &source.checksum {
+        let actual = checksum::hash_bytes(&bytes, expected.r#type);
+        if actual != expected.checksum {
+            return Err(anyhow!(
+                "Checksum mismatch for {}: expected {} ({}) but got {}",
+                source.url,
+                expected.checksum,
+                expected.r#type.as_str(),
+                actual
+            ));
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use crate::checksum::ChecksumType;
+    use crate::config::{SourceChecksum, SourceRel};
+    use url::Url;
+
+    #[test]
+    fn test_fetch_with_no_sources_errors() {
+        let result = fetch(&[]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_fetch_falls_back_to_next_source_on_failure() -> Result<()> {
+        let mut server = mockito::Server::new();
+        let _dead = server.mock("GET", "/dead").with_status(500).create();
+        let _alive = server
+            .mock("GET", "/alive")
+            .with_status(200)
+            .with_body("hello world")
+            .create();
+
+        let sources = vec![
+            FileSource::new(&Url::parse(&format!("{}/dead", server.url()))?, SourceRel::Repository),
+            FileSource::new(&Url::parse(&format!("{}/alive", server.url()))?, SourceRel::Mirror),
+        ];
+        assert_eq!(fetch(&sources)?, b"hello world");
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_skips_source_with_checksum_mismatch() -> Result<()> {
+        let mut server = mockito::Server::new();
+        let _tampered = server
+            .mock("GET", "/tampered")
+            .with_status(200)
+            .with_body("tampered bytes")
+            .create();
+        let _good = server
+            .mock("GET", "/good")
+            .with_status(200)
+            .with_body("hello world")
+            .create();
+
+        let mut tampered =
+            FileSource::new(&Url::parse(&format!("{}/tampered", server.url()))?, SourceRel::Mirror);
+        tampered.checksum = Some(SourceChecksum {
+            checksum: checksum::hash_bytes(b"hello world", ChecksumType::Sha256),
+            r#type: ChecksumType::Sha256,
+        });
+        let good = FileSource::new(&Url::parse(&format!("{}/good", server.url()))?, SourceRel::Repository);
+
+        assert_eq!(fetch(&[tampered, good])?, b"hello world");
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::config;
+use crate::content::InlineContent;
 
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
@@ -103,7 +104,7 @@ impl ServiceInfo {
 // https://editor.swagger.io/?url=https://raw.githubusercontent.com/ga4gh/tool-registry-schemas/develop/openapi/openapi.yaml
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-struct Checksum {
+pub(crate) struct Checksum {
     pub checksum: String,
     pub r#type: String,
 }
@@ -126,6 +127,11 @@ struct ToolFile {
     pub file_type: Option<FileType>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub r#type: Option<String>,
+    /// gh-trs extension: every mirror URL a `config::FileSource` list
+    /// resolved to, so clients get the same fallback resilience as the
+    /// downloader.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub urls: Option<Vec<Url>>,
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
@@ -204,7 +210,7 @@ struct ImageData {
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-enum ImageType {
+pub(crate) enum ImageType {
     Docker,
     Singularity,
     Conda,
@@ -212,7 +218,7 @@ enum ImageType {
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
-enum DescriptorType {
+pub(crate) enum DescriptorType {
     Cwl,
     Wdl,
     Nfl,
@@ -238,7 +244,7 @@ enum DescriptorTypeWithPlain {
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 struct FileWrapper {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub content: Option<String>,
+    pub content: Option<InlineContent>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub checksum: Option<Vec<Checksum>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -247,6 +253,46 @@ struct FileWrapper {
 
 // --- Type definition end ---
 
+impl ToolFile {
+    /// Build a `ToolFile` for `target`, surfacing every mirror URL recorded
+    /// on `sources`.
+    fn new(target: PathBuf, file_type: FileType, sources: &[config::FileSource]) -> Self {
+        let urls = sources.iter().map(|source| source.url.clone()).collect();
+        Self {
+            path: Some(target),
+            file_type: Some(file_type),
+            r#type: None,
+            urls: Some(urls),
+        }
+    }
+}
+
+impl FileWrapper {
+    /// Build a `FileWrapper` for `sources`, trying mirrors in order via
+    /// `download::fetch` and computing a checksum for every algorithm
+    /// configured in `config.checksum` from the bytes it returns.
+    fn new(sources: &[config::FileSource], config: &config::Config) -> Result<Self> {
+        let checksum = if config.checksum.is_empty() {
+            None
+        } else {
+            let bytes = crate::download::fetch(sources)?;
+            Some(crate::checksum::generate_checksums(&bytes, &config.checksum))
+        };
+        Ok(Self {
+            content: None,
+            checksum,
+            url: sources.first().map(|source| source.url.clone()),
+        })
+    }
+
+    /// Inline `bytes` directly into `content` so TRS clients that don't want
+    /// to chase `url` can read the descriptor without another request.
+    fn with_content(mut self, bytes: impl Into<Vec<u8>>) -> Self {
+        self.content = Some(InlineContent::new(bytes));
+        self
+    }
+}
+
 impl Default for ToolClass {
     fn default() -> Self {
         ToolClass {
@@ -296,6 +342,22 @@ impl ToolVersion {
     }
 }
 
+impl ImageData {
+    /// Attach a checksum fetched from `manifest_url` (e.g. a registry's
+    /// image manifest or blob endpoint), using the first algorithm in
+    /// `config.checksum`.
+    pub(crate) fn with_checksum(mut self, manifest_url: &Url, config: &config::Config) -> Result<Self> {
+        if config.checksum.is_empty() {
+            return Ok(self);
+        }
+        let source = config::FileSource::new(manifest_url, config::SourceRel::Repository);
+        let bytes = crate::download::fetch(std::slice::from_ref(&source))?;
+        let checksums = crate::checksum::generate_checksums(&bytes, &config.checksum);
+        self.checksum = checksums.into_iter().next();
+        Ok(self)
+    }
+}
+
 impl DescriptorType {
     fn new(wf_type: &config::LanguageType) -> Self {
         match wf_type {
@@ -306,3 +368,125 @@ impl DescriptorType {
         }
     }
 }
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use crate::checksum::ChecksumType;
+    use crate::config::{FileSource, SourceRel};
+
+    fn test_config(checksum: Vec<ChecksumType>) -> config::Config {
+        config::Config {
+            id: Uuid::new_v4(),
+            version: "1.0.0".to_string(),
+            license: "MIT".to_string(),
+            authors: vec!["octocat".to_string()],
+            workflow: config::Workflow {
+                name: "test-workflow".to_string(),
+                readme: Url::parse("https://example.com/README.md").unwrap(),
+                language: config::Language {
+                    r#type: Some(config::LanguageType::Cwl),
+                    version: None,
+                },
+                files: vec![],
+                testing: vec![],
+            },
+            checksum,
+        }
+    }
+
+    fn empty_image_data() -> ImageData {
+        ImageData {
+            registry_host: None,
+            image_name: None,
+            size: None,
+            updated: None,
+            checksum: None,
+            image_type: None,
+        }
+    }
+
+    #[test]
+    fn test_tool_file_new_surfaces_all_urls() -> Result<()> {
+        let sources = vec![
+            FileSource::new(&Url::parse("https://example.com/main.cwl")?, SourceRel::Repository),
+            FileSource::new(&Url::parse("https://mirror.example.com/main.cwl")?, SourceRel::Mirror),
+        ];
+        let tool_file = ToolFile::new(PathBuf::from("main.cwl"), FileType::PrimaryDescriptor, &sources);
+        assert_eq!(tool_file.path, Some(PathBuf::from("main.cwl")));
+        assert_eq!(
+            tool_file.urls,
+            Some(vec![
+                Url::parse("https://example.com/main.cwl")?,
+                Url::parse("https://mirror.example.com/main.cwl")?,
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_wrapper_new_without_checksum_does_not_fetch() -> Result<()> {
+        let sources = vec![FileSource::new(&Url::parse("https://example.com/main.cwl")?, SourceRel::Repository)];
+        let config = test_config(vec![]);
+        let wrapper = FileWrapper::new(&sources, &config)?;
+        assert_eq!(wrapper.checksum, None);
+        assert_eq!(wrapper.url, Some(Url::parse("https://example.com/main.cwl")?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_wrapper_new_with_checksum_fetches_and_hashes() -> Result<()> {
+        let mut server = mockito::Server::new();
+        let _mock = server.mock("GET", "/main.cwl").with_status(200).with_body("hello world").create();
+
+        let sources = vec![FileSource::new(&Url::parse(&format!("{}/main.cwl", server.url()))?, SourceRel::Repository)];
+        let config = test_config(vec![ChecksumType::Sha256]);
+        let wrapper = FileWrapper::new(&sources, &config)?;
+
+        assert_eq!(
+            wrapper.checksum,
+            Some(vec![Checksum {
+                checksum: "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9".to_string(),
+                r#type: "sha-256".to_string(),
+            }])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_wrapper_with_content_inlines_bytes() -> Result<()> {
+        let sources = vec![FileSource::new(&Url::parse("https://example.com/main.cwl")?, SourceRel::Repository)];
+        let config = test_config(vec![]);
+        let wrapper = FileWrapper::new(&sources, &config)?.with_content(b"the descriptor".to_vec());
+        assert_eq!(wrapper.content, Some(InlineContent::new(b"the descriptor".to_vec())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_image_data_with_checksum_noop_when_disabled() -> Result<()> {
+        let config = test_config(vec![]);
+        let image = empty_image_data().with_checksum(&Url::parse("https://example.com/image")?, &config)?;
+        assert_eq!(image.checksum, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_image_data_with_checksum_fetches_and_hashes() -> Result<()> {
+        let mut server = mockito::Server::new();
+        let _mock = server.mock("GET", "/image").with_status(200).with_body("hello world").create();
+
+        let config = test_config(vec![ChecksumType::Sha256]);
+        let image = empty_image_data()
+            .with_checksum(&Url::parse(&format!("{}/image", server.url()))?, &config)?;
+
+        assert_eq!(
+            image.checksum,
+            Some(Checksum {
+                checksum: "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9".to_string(),
+                r#type: "sha-256".to_string(),
+            })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use crate::trs::{DescriptorType, ImageType};
+
+use serde::Serialize;
+
+/// The TRS protocol version this build implements, as a semver tuple.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub struct ProtocolVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl ProtocolVersion {
+    const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+}
+
+fn is_false(enabled: &bool) -> bool {
+    !enabled
+}
+
+/// What this gh-trs build can actually do.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Capabilities {
+    pub descriptor_types: Vec<DescriptorType>,
+    #[serde(skip_serializing_if = "is_false")]
+    pub checksum: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub content_inlining: bool,
+    pub image_types: Vec<ImageType>,
+}
+
+/// Reported by the `version` subcommand.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Version {
+    pub gh_trs_version: String,
+    pub trs_protocol_version: ProtocolVersion,
+    pub capabilities: Capabilities,
+}
+
+impl Version {
+    pub fn new() -> Self {
+        Self {
+            gh_trs_version: env!("CARGO_PKG_VERSION").to_string(),
+            trs_protocol_version: ProtocolVersion::new(2, 0, 1),
+            capabilities: Capabilities {
+                descriptor_types: vec![
+                    DescriptorType::Cwl,
+                    DescriptorType::Wdl,
+                    DescriptorType::Nfl,
+                    DescriptorType::Smk,
+                    DescriptorType::Galaxy,
+                ],
+                checksum: true,
+                content_inlining: true,
+                image_types: vec![ImageType::Docker, ImageType::Singularity, ImageType::Conda],
+            },
+        }
+    }
+}
+
+impl Default for Version {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_capabilities_are_populated() {
+        let version = Version::new();
+        assert_eq!(version.capabilities.descriptor_types.len(), 5);
+        assert!(version.capabilities.checksum);
+        assert!(version.capabilities.content_inlining);
+        assert_eq!(version.capabilities.image_types.len(), 3);
+    }
+
+    #[test]
+    fn test_version_serializes_capabilities() -> Result<(), serde_json::Error> {
+        let json = serde_json::to_string(&Version::new())?;
+        assert!(json.contains("\"checksum\":true"));
+        assert!(json.contains("\"content_inlining\":true"));
+        Ok(())
+    }
+}